@@ -10,32 +10,148 @@
 #![no_main]
 
 use embassy_executor::Spawner;
+use embassy_futures::join::join3;
 use embassy_time::{Duration, Timer, Instant};
 use embassy_sync::{blocking_mutex::raw::ThreadModeRawMutex, mutex::Mutex};
 use embassy_rp::{
-    gpio::{Level, Output, Input, Pull},
-    uart::{self, Uart, Config as UartConfig, Blocking},
+    flash::{Blocking as FlashBlocking, Flash},
+    gpio::{Level, Output, Input, Pull, Flex},
+    pio::{self, Pio},
+    pio_programs::ws2812::{PioWs2812, PioWs2812Program},
+    pwm::{Config as PwmConfig, Pwm},
+    uart::{self, Uart, UartTx, UartRx, Config as UartConfig, Blocking},
     peripherals::*,
     bind_interrupts,
 };
+use smart_leds::RGB8;
+use fixed::FixedU16;
+use fixed::types::extra::U4;
 use {defmt_rtt as _, panic_probe as _};
 use defmt::*;
+use core::fmt::Write as _;
 
 // Game state
 static SCORE: Mutex<ThreadModeRawMutex, u32> = Mutex::new(0);
 
+// Tunables, changeable live over the serial console and read by game_logic_task each shot.
+#[derive(Clone, Copy)]
+struct GameConfig {
+    ball_threshold_cm: u16,
+    ring_threshold_cm: u16,
+    good_window_ms: u64,
+    miss_window_ms: u64,
+}
+
+impl GameConfig {
+    const fn defaults() -> Self {
+        Self {
+            ball_threshold_cm: 50,
+            ring_threshold_cm: 60,
+            good_window_ms: 2000,
+            miss_window_ms: 3000,
+        }
+    }
+}
+
+// Bumped whenever the stored blob layout changes, so a stale/uninitialized
+// flash sector from an older firmware revision is never mistaken for valid data.
+const CONFIG_REVISION: u8 = 1;
+
+// RP2040 Pico boards ship with 2MB of QSPI flash; settings live in its last sector.
+const FLASH_SIZE: usize = 2 * 1024 * 1024;
+const SETTINGS_SECTOR: u32 = (FLASH_SIZE - FLASH_ERASE_SIZE) as u32;
+const FLASH_ERASE_SIZE: usize = 4096;
+const FLASH_WRITE_SIZE: usize = 256; // flash writes must be page-aligned
+
+fn encode_settings(config: &GameConfig, high_score: u32) -> [u8; FLASH_WRITE_SIZE] {
+    let mut buf = [0u8; FLASH_WRITE_SIZE];
+    buf[0] = CONFIG_REVISION;
+    buf[1..3].copy_from_slice(&config.ball_threshold_cm.to_le_bytes());
+    buf[3..5].copy_from_slice(&config.ring_threshold_cm.to_le_bytes());
+    buf[5..13].copy_from_slice(&config.good_window_ms.to_le_bytes());
+    buf[13..21].copy_from_slice(&config.miss_window_ms.to_le_bytes());
+    buf[21..25].copy_from_slice(&high_score.to_le_bytes());
+    buf
+}
+
+fn decode_settings(buf: &[u8; FLASH_WRITE_SIZE]) -> Option<(GameConfig, u32)> {
+    if buf[0] != CONFIG_REVISION {
+        return None;
+    }
+
+    let config = GameConfig {
+        ball_threshold_cm: u16::from_le_bytes([buf[1], buf[2]]),
+        ring_threshold_cm: u16::from_le_bytes([buf[3], buf[4]]),
+        good_window_ms: u64::from_le_bytes(buf[5..13].try_into().unwrap()),
+        miss_window_ms: u64::from_le_bytes(buf[13..21].try_into().unwrap()),
+    };
+    let high_score = u32::from_le_bytes(buf[21..25].try_into().unwrap());
+
+    Some((config, high_score))
+}
+
+struct Settings {
+    flash: Flash<'static, FLASH, FlashBlocking, FLASH_SIZE>,
+}
+
+impl Settings {
+    fn new(flash: Flash<'static, FLASH, FlashBlocking, FLASH_SIZE>) -> Self {
+        Self { flash }
+    }
+
+    // Loads the stored config/high score, falling back to defaults if the
+    // revision byte doesn't match or the blob can't be read.
+    async fn load(&mut self) -> (GameConfig, u32) {
+        let mut buf = [0u8; FLASH_WRITE_SIZE];
+        if self.flash.blocking_read(SETTINGS_SECTOR, &mut buf).is_ok() {
+            if let Some(loaded) = decode_settings(&buf) {
+                info!("Settings: loaded (revision {})", CONFIG_REVISION);
+                return loaded;
+            }
+        }
+
+        info!("Settings: no valid stored data, using defaults");
+        (GameConfig::defaults(), 0)
+    }
+
+    async fn store(&mut self, config: GameConfig, high_score: u32) {
+        let buf = encode_settings(&config, high_score);
+        let erase_result = self
+            .flash
+            .blocking_erase(SETTINGS_SECTOR, SETTINGS_SECTOR + FLASH_ERASE_SIZE as u32);
+        if erase_result.is_ok() {
+            let _ = self.flash.blocking_write(SETTINGS_SECTOR, &buf);
+        }
+    }
+}
+
+static CONFIG: Mutex<ThreadModeRawMutex, GameConfig> = Mutex::new(GameConfig::defaults());
+
+// All-time best, persisted to flash alongside CONFIG.
+static HIGH_SCORE: Mutex<ThreadModeRawMutex, u32> = Mutex::new(0);
+
+// Shared UART TX half so game_logic_task can emit telemetry while serial_task
+// owns the RX half and handles incoming commands.
+static UART_TX: Mutex<ThreadModeRawMutex, Option<UartTx<'static, UART0, Blocking>>> = Mutex::new(None);
+
+// Persisted settings, stored in the QSPI flash's last sector. Shared so
+// game_logic_task can persist a new high score without owning the flash peripheral.
+static SETTINGS: Mutex<ThreadModeRawMutex, Option<Settings>> = Mutex::new(None);
+
 bind_interrupts!(
     struct Irqs {
         UART0_IRQ => uart::InterruptHandler<UART0>;
         UART1_IRQ => uart::InterruptHandler<UART1>;
+        PIO0_IRQ_0 => pio::InterruptHandler<PIO0>;
     }
 );
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum ShotResult {
-    Miss,      // Red
-    Good,      // Blue  
-    Perfect,   // Green
+    Idle,      // Rainbow, waiting for a ball
+    Miss,      // Red flash
+    Good,      // Blue pulse
+    Perfect,   // Green comet
 }
 
 #[embassy_executor::main]
@@ -71,23 +187,55 @@ async fn main(spawner: Spawner) {
 
     // TM1637 4-digit 7-segment Display (clk=21, dio=22)
     let display = TM1637Display::new(
-        Output::new(peripherals.PIN_21, Level::Low), // CLK
-        Output::new(peripherals.PIN_22, Level::Low), // DIO
+        Output::new(peripherals.PIN_21, Level::High), // CLK
+        Flex::new(peripherals.PIN_22),                // DIO (needs to read the ACK bit)
+    );
+
+    // WS2812 LED ring (data on pin 17), PIO-driven so animations don't block the executor
+    let Pio { mut common, sm0, .. } = Pio::new(peripherals.PIO0, Irqs);
+    let ws2812_program = PioWs2812Program::new(&mut common);
+    let ws2812 = PioWs2812::new(
+        &mut common,
+        sm0,
+        peripherals.DMA_CH0,
+        peripherals.PIN_17,
+        &ws2812_program,
     );
+    let leds = LedRing::new(ws2812);
 
-    // RGB LEDs (pins 17, 19, 20)
-    let leds = RgbLeds::new(
-        Output::new(peripherals.PIN_17, Level::Low), // Red
-        Output::new(peripherals.PIN_19, Level::Low), // Green
-        Output::new(peripherals.PIN_20, Level::Low), // Blue
+    // Piezo buzzer (pin 6), PWM-driven
+    let mut buzzer_config = PwmConfig::default();
+    buzzer_config.top = 0; // set per-tone by play_tone()
+    let buzzer_pwm = Pwm::new_output_a(peripherals.PWM_SLICE3, peripherals.PIN_6, buzzer_config.clone());
+    let buzzer = Buzzer::new(PwmChannel::new(buzzer_pwm, buzzer_config));
+
+    // Serial command console / telemetry (tx=0, rx=1). Blocking mode is plenty
+    // for a line-at-a-time console and needs no DMA channels.
+    let uart = Uart::new_blocking(
+        peripherals.UART0,
+        peripherals.PIN_0,
+        peripherals.PIN_1,
+        UartConfig::default(),
     );
+    let (uart_tx, uart_rx) = uart.split();
+    *UART_TX.lock().await = Some(uart_tx);
+
+    // Persisted config + all-time high score, reloaded so a power cycle doesn't
+    // reset everything back to defaults.
+    let mut settings = Settings::new(Flash::<_, FlashBlocking, FLASH_SIZE>::new_blocking(peripherals.FLASH));
+    let (loaded_config, loaded_high_score) = settings.load().await;
+    *CONFIG.lock().await = loaded_config;
+    *HIGH_SCORE.lock().await = loaded_high_score;
+    *SETTINGS.lock().await = Some(settings);
 
     info!("Hardware initialized");
 
     // Spawn tasks
     spawner.spawn(game_logic_task(hc_sr04, ring_sensor1, ring_sensor2, ring_sensor3)).unwrap();
-    spawner.spawn(display_task(display)).unwrap();
+    spawner.spawn(display_task(display, loaded_high_score)).unwrap();
     spawner.spawn(led_task(leds)).unwrap();
+    spawner.spawn(buzzer_task(buzzer)).unwrap();
+    spawner.spawn(serial_task(uart_rx)).unwrap();
 
     info!("Ready to play!");
 
@@ -108,64 +256,124 @@ async fn game_logic_task(
 ) {
     info!("Game logic started");
     
-    let mut current_result = ShotResult::Miss;
+    let mut current_result = ShotResult::Idle;
     
     loop {
+        let config = *CONFIG.lock().await;
+
         // Wait for HC-SR04 to detect ball
-        if let Ok(distance) = hc_sr04.read_distance().await {
-            if distance < 50 { // Ball detected in front of hoop
+        if let Ok(distance) = hc_sr04.read_distance_filtered().await {
+            if distance < config.ball_threshold_cm { // Ball detected in front of hoop
                 info!("Ball detected at {}cm! Starting timer...", distance);
-                
+
                 let shot_start = Instant::now();
                 let mut ball_detected_in_ring = false;
-                
-                // Monitor ring sensors for max 3 seconds
-                while shot_start.elapsed() < Duration::from_secs(3) && !ball_detected_in_ring {
-                    // Check the 3 ring sensors
-                    let ring1_dist = ring1.read_distance().await.unwrap_or(400);
-                    let ring2_dist = ring2.read_distance().await.unwrap_or(400);
-                    let ring3_dist = ring3.read_distance().await.unwrap_or(400);
-                    
+                let miss_window = Duration::from_millis(config.miss_window_ms);
+                let good_window = Duration::from_millis(config.good_window_ms);
+                let mut elapsed = Duration::from_millis(0);
+
+                // Monitor ring sensors for max `miss_window`
+                while shot_start.elapsed() < miss_window && !ball_detected_in_ring {
+                    // Fire all 3 triggers and await their echoes concurrently - the
+                    // ball is only in the ring for tens of milliseconds.
+                    let (r1, r2, r3) = join3(
+                        ring1.read_distance_filtered(),
+                        ring2.read_distance_filtered(),
+                        ring3.read_distance_filtered(),
+                    )
+                    .await;
+
+                    let ring1_dist = r1.unwrap_or(400);
+                    let ring2_dist = r2.unwrap_or(400);
+                    let ring3_dist = r3.unwrap_or(400);
+
                     // Detect if ball passed through ring (short distance)
-                    if ring1_dist < 60 || ring2_dist < 60 || ring3_dist < 60 {
+                    let under_threshold = ring1_dist < config.ring_threshold_cm
+                        || ring2_dist < config.ring_threshold_cm
+                        || ring3_dist < config.ring_threshold_cm;
+
+                    // Confirm with a second sample taken right away, back-to-back,
+                    // rather than on the next 50ms poll - the ball is only in the
+                    // ring for tens of milliseconds and would be gone by then.
+                    let confirmed = if under_threshold {
+                        Timer::after(Duration::from_millis(10)).await;
+
+                        let (c1, c2, c3) = join3(
+                            ring1.read_distance_filtered(),
+                            ring2.read_distance_filtered(),
+                            ring3.read_distance_filtered(),
+                        )
+                        .await;
+
+                        let confirm1_dist = c1.unwrap_or(400);
+                        let confirm2_dist = c2.unwrap_or(400);
+                        let confirm3_dist = c3.unwrap_or(400);
+
+                        confirm1_dist < config.ring_threshold_cm
+                            || confirm2_dist < config.ring_threshold_cm
+                            || confirm3_dist < config.ring_threshold_cm
+                    } else {
+                        false
+                    };
+
+                    if confirmed {
                         ball_detected_in_ring = true;
-                        let elapsed = shot_start.elapsed();
-                        
-                        if elapsed < Duration::from_secs(2) {
-                            // Under 2 seconds -> Green LEDs (Perfect)
+                        elapsed = shot_start.elapsed();
+
+                        if elapsed < good_window {
+                            // Under the good window -> Green LEDs (Perfect)
                             current_result = ShotResult::Perfect;
                             info!("PERFECT SHOT! Time: {}ms", elapsed.as_millis());
                         } else {
-                            // 2+ seconds -> Blue LEDs (Good) 
+                            // At/over the good window -> Blue LEDs (Good)
                             current_result = ShotResult::Good;
                             info!("GOOD SHOT! Time: {}ms", elapsed.as_millis());
                         }
-                        
+
                         // Increment score
-                        {
+                        let new_total = {
                             let mut score = SCORE.lock().await;
                             *score += 1;
                             info!("SCORE! New total: {}", *score);
+                            *score
+                        };
+
+                        // New all-time high -> persist it to flash
+                        let is_new_high = {
+                            let mut high_score = HIGH_SCORE.lock().await;
+                            if new_total > *high_score {
+                                *high_score = new_total;
+                                true
+                            } else {
+                                false
+                            }
+                        };
+                        if is_new_high {
+                            persist_high_score(new_total).await;
                         }
-                        
+
                         break;
                     }
-                    
+
                     Timer::after(Duration::from_millis(50)).await;
                 }
-                
-                // If nothing detected in 3 seconds -> Miss (red)
+
+                // If nothing detected in the miss window -> Miss (red)
                 if !ball_detected_in_ring {
                     current_result = ShotResult::Miss;
+                    elapsed = shot_start.elapsed();
                     info!("MISS! No detection in ring sensors");
                 }
-                
+
+                let total = *SCORE.lock().await;
+                log_shot(current_result, elapsed.as_millis(), total).await;
+
                 // Display result for 2 seconds
                 set_global_result(current_result).await;
                 Timer::after(Duration::from_secs(2)).await;
                 
                 // Reset to idle
-                set_global_result(ShotResult::Miss).await;
+                set_global_result(ShotResult::Idle).await;
                 Timer::after(Duration::from_secs(1)).await; // Pause between shots
             }
         }
@@ -175,22 +383,187 @@ async fn game_logic_task(
 }
 
 // Global result for inter-task communication
-static CURRENT_RESULT: Mutex<ThreadModeRawMutex, ShotResult> = Mutex::new(ShotResult::Miss);
+static CURRENT_RESULT: Mutex<ThreadModeRawMutex, ShotResult> = Mutex::new(ShotResult::Idle);
 
 async fn set_global_result(result: ShotResult) {
     let mut current = CURRENT_RESULT.lock().await;
     *current = result;
 }
 
+// Small fixed-capacity buffer so telemetry lines can be built with `write!`
+// without needing an allocator.
+struct LineBuf {
+    buf: [u8; 64],
+    len: usize,
+}
+
+impl LineBuf {
+    fn new() -> Self {
+        Self { buf: [0; 64], len: 0 }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl core::fmt::Write for LineBuf {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let bytes = s.as_bytes();
+        let end = (self.len + bytes.len()).min(self.buf.len());
+        let n = end - self.len;
+        self.buf[self.len..end].copy_from_slice(&bytes[..n]);
+        self.len = end;
+        Ok(())
+    }
+}
+
+// Persists the current config alongside a new high score.
+async fn persist_high_score(high_score: u32) {
+    let config = *CONFIG.lock().await;
+    let mut guard = SETTINGS.lock().await;
+    if let Some(settings) = guard.as_mut() {
+        settings.store(config, high_score).await;
+        info!("Settings: stored new high score {}", high_score);
+    }
+}
+
+// Formats `args` into a `LineBuf` and writes it out over UART. Shared by the
+// automatic shot telemetry and the serial console's command replies.
+async fn uart_write_fmt(args: core::fmt::Arguments<'_>) {
+    let mut guard = UART_TX.lock().await;
+    if let Some(tx) = guard.as_mut() {
+        let mut line = LineBuf::new();
+        let _ = line.write_fmt(args);
+        let _ = tx.blocking_write(line.as_bytes());
+    }
+}
+
+// Writes a scoring telemetry line out over UART, e.g. "SHOT Perfect 1840ms total=7".
+async fn log_shot(result: ShotResult, elapsed_ms: u64, total: u32) {
+    let label = match result {
+        ShotResult::Perfect => "Perfect",
+        ShotResult::Good => "Good",
+        ShotResult::Miss => "Miss",
+        ShotResult::Idle => "Idle",
+    };
+
+    uart_write_fmt(format_args!("SHOT {} {}ms total={}\r\n", label, elapsed_ms, total)).await;
+}
+
+// Upper bound for a console-configured shot window, so `miss_window_ms`
+// (good_window_ms + 1000) can never overflow before it's stored/persisted.
+const MAX_WINDOW_MS: u64 = 60_000;
+
+// Parsed serial console commands.
+enum Command {
+    Reset,
+    Score,
+    Threshold(u16),
+    Window(u64),
+    Unknown,
+}
+
+fn parse_command(line: &str) -> Command {
+    let mut parts = line.trim().split_whitespace();
+    match parts.next() {
+        Some("reset") => Command::Reset,
+        Some("score") => Command::Score,
+        Some("threshold") => parts
+            .next()
+            .and_then(|v| v.parse().ok())
+            .map(Command::Threshold)
+            .unwrap_or(Command::Unknown),
+        Some("window") => parts
+            .next()
+            .and_then(|v| v.parse().ok())
+            .map(Command::Window)
+            .unwrap_or(Command::Unknown),
+        _ => Command::Unknown,
+    }
+}
+
+async fn handle_command(line: &str) {
+    match parse_command(line) {
+        Command::Reset => {
+            let mut score = SCORE.lock().await;
+            *score = 0;
+            info!("Serial: score reset");
+            uart_write_fmt(format_args!("OK reset\r\n")).await;
+        }
+        Command::Score => {
+            let score = *SCORE.lock().await;
+            info!("Serial: score = {}", score);
+            uart_write_fmt(format_args!("SCORE {}\r\n", score)).await;
+        }
+        Command::Threshold(cm) => {
+            let mut config = CONFIG.lock().await;
+            config.ball_threshold_cm = cm;
+            config.ring_threshold_cm = cm;
+            info!("Serial: threshold set to {}cm", cm);
+            uart_write_fmt(format_args!("OK threshold={}\r\n", cm)).await;
+        }
+        Command::Window(ms) => {
+            // Clamp to a sane shot window before it's stored (and later
+            // persisted to flash) - `ms` comes straight from the serial
+            // console and an unchecked `ms + 1000` can overflow a u64.
+            let ms = ms.min(MAX_WINDOW_MS);
+            let mut config = CONFIG.lock().await;
+            config.good_window_ms = ms;
+            config.miss_window_ms = ms.saturating_add(1000);
+            info!("Serial: window set to {}ms", ms);
+            uart_write_fmt(format_args!("OK window={}\r\n", ms)).await;
+        }
+        Command::Unknown => {
+            warn!("Serial: unrecognized command");
+            uart_write_fmt(format_args!("ERR unknown command\r\n")).await;
+        }
+    }
+}
+
 #[embassy_executor::task]
-async fn display_task(mut display: TM1637Display) {
+async fn serial_task(mut rx: UartRx<'static, UART0, Blocking>) {
+    info!("Serial console started");
+
+    let mut buf = [0u8; 64];
+    let mut len = 0usize;
+
+    loop {
+        let mut byte = [0u8; 1];
+        if rx.blocking_read(&mut byte).is_ok() {
+            match byte[0] {
+                b'\n' | b'\r' => {
+                    if len > 0 {
+                        if let Ok(line) = core::str::from_utf8(&buf[..len]) {
+                            handle_command(line).await;
+                        }
+                        len = 0;
+                    }
+                }
+                b => {
+                    if len < buf.len() {
+                        buf[len] = b;
+                        len += 1;
+                    }
+                }
+            }
+        }
+
+        Timer::after(Duration::from_millis(5)).await;
+    }
+}
+
+#[embassy_executor::task]
+async fn display_task(mut display: TM1637Display, high_score: u32) {
     info!("Display task started");
-    
-    // Startup sequence
+
+    // Startup sequence: greet, briefly show the stored high score, then reset to live score.
     display.show_text("HOOP").await;
     Timer::after(Duration::from_secs(1)).await;
+    display.show_number(high_score).await;
+    Timer::after(Duration::from_secs(1)).await;
     display.show_number(0).await;
-    
+
     loop {
         let score = SCORE.lock().await;
         display.show_number(*score).await;
@@ -201,33 +574,89 @@ async fn display_task(mut display: TM1637Display) {
 }
 
 #[embassy_executor::task]
-async fn led_task(mut leds: RgbLeds) {
+async fn led_task(mut ring: LedRing<'static>) {
     info!("LED task started");
-    
+
     // Startup animation - purple
-    leds.set_color([128, 0, 128]).await; // Purple
+    ring.fill(RGB8::new(128, 0, 128)).await;
     Timer::after(Duration::from_secs(1)).await;
-    leds.clear().await;
-    
+    ring.clear().await;
+
+    let mut comet_pos: usize = 0;
+    let mut rainbow_hue: u8 = 0;
+
     loop {
-        let result = CURRENT_RESULT.lock().await;
-        
-        match *result {
+        let result = *CURRENT_RESULT.lock().await;
+
+        match result {
+            ShotResult::Idle => {
+                // Slow rainbow while waiting for a ball
+                for i in 0..NUM_PIXELS {
+                    let pixel_hue = rainbow_hue.wrapping_add((i * 256 / NUM_PIXELS) as u8);
+                    ring.set_pixel(i, wheel(pixel_hue));
+                }
+                ring.render().await;
+                rainbow_hue = rainbow_hue.wrapping_add(2);
+                Timer::after(Duration::from_millis(40)).await;
+            }
             ShotResult::Miss => {
-                // Red LEDs for miss or idle
-                leds.set_color([255, 0, 0]).await;
+                // Red flash
+                ring.fill(RGB8::new(255, 0, 0)).await;
+                Timer::after(Duration::from_millis(150)).await;
+                ring.clear().await;
+                Timer::after(Duration::from_millis(150)).await;
             }
             ShotResult::Good => {
-                // Blue LEDs for good shot (2+ seconds)
-                leds.set_color([0, 0, 255]).await;
+                // Blue pulse
+                for &level in &[40u8, 110, 200, 255, 200, 110, 40] {
+                    ring.fill(RGB8::new(0, 0, level)).await;
+                    Timer::after(Duration::from_millis(60)).await;
+                }
             }
             ShotResult::Perfect => {
-                // Green LEDs for perfect shot (under 2 seconds)
-                leds.set_color([0, 255, 0]).await;
+                // Spinning green comet with a fading tail
+                for i in 0..NUM_PIXELS {
+                    let dist = (comet_pos + NUM_PIXELS - i) % NUM_PIXELS;
+                    let brightness = 255u8.saturating_sub((dist * 80).min(255) as u8);
+                    ring.set_pixel(i, RGB8::new(0, brightness, 0));
+                }
+                ring.render().await;
+                comet_pos = (comet_pos + 1) % NUM_PIXELS;
+                Timer::after(Duration::from_millis(60)).await;
             }
         }
-        
-        Timer::after(Duration::from_millis(100)).await;
+    }
+}
+
+#[embassy_executor::task]
+async fn buzzer_task(mut buzzer: Buzzer) {
+    info!("Buzzer task started");
+
+    let mut last_result = ShotResult::Idle;
+
+    loop {
+        let result = *CURRENT_RESULT.lock().await;
+
+        if result != last_result {
+            match result {
+                ShotResult::Perfect => {
+                    info!("Buzzer: perfect arpeggio");
+                    for &freq in &TONE_TABLE {
+                        buzzer.play_tone(freq, Duration::from_millis(80)).await;
+                    }
+                }
+                ShotResult::Good => {
+                    buzzer.play_tone(330, Duration::from_millis(150)).await;
+                }
+                ShotResult::Miss => {
+                    buzzer.play_tone(196, Duration::from_millis(300)).await;
+                }
+                ShotResult::Idle => {}
+            }
+            last_result = result;
+        }
+
+        Timer::after(Duration::from_millis(50)).await;
     }
 }
 
@@ -275,9 +704,25 @@ impl HcSr04Sensor {
         
         let echo_duration = echo_start.elapsed().as_micros();
         let distance_cm = (echo_duration as f32 * 0.01715) as u16;
-        
+
         Ok(distance_cm.min(400))
     }
+
+    // Takes SAMPLE_COUNT rapid readings and returns their median, discarding
+    // timed-out echoes first so a single dropped reading doesn't poison the result.
+    async fn read_distance_filtered(&mut self) -> Result<u16, ()> {
+        let mut samples = [0u16; SAMPLE_COUNT];
+        let mut count = 0;
+
+        for _ in 0..SAMPLE_COUNT {
+            if let Ok(distance) = self.read_distance().await {
+                samples[count] = distance;
+                count += 1;
+            }
+        }
+
+        median_of(&mut samples[..count])
+    }
 }
 
 struct IoeSr05Sensor {
@@ -325,84 +770,306 @@ impl IoeSr05Sensor {
         
         let echo_duration = echo_start.elapsed().as_micros();
         let distance_cm = (echo_duration as f32 * 0.01715) as u16;
-        
+
         Ok(distance_cm.min(400))
     }
+
+    // Takes SAMPLE_COUNT rapid readings and returns their median, discarding
+    // timed-out echoes first so a single dropped reading doesn't poison the result.
+    async fn read_distance_filtered(&mut self) -> Result<u16, ()> {
+        let mut samples = [0u16; SAMPLE_COUNT];
+        let mut count = 0;
+
+        for _ in 0..SAMPLE_COUNT {
+            if let Ok(distance) = self.read_distance().await {
+                samples[count] = distance;
+                count += 1;
+            }
+        }
+
+        median_of(&mut samples[..count])
+    }
+}
+
+// Number of rapid readings averaged (by median) into one filtered distance.
+const SAMPLE_COUNT: usize = 5;
+
+fn median_of(samples: &mut [u16]) -> Result<u16, ()> {
+    if samples.is_empty() {
+        return Err(());
+    }
+    samples.sort_unstable();
+    Ok(samples[samples.len() / 2])
+}
+
+// TM1637 command bytes (see datasheet section 3)
+const CMD_DATA_AUTO_INCREMENT: u8 = 0x40;
+const CMD_ADDRESS_BASE: u8 = 0xC0;
+const CMD_DISPLAY_CONTROL: u8 = 0x88; // on, OR'd with brightness 0-7
+const DEFAULT_BRIGHTNESS: u8 = 0x07;
+
+// 7-segment patterns for digits 0-9, plus a trailing dash.
+const DIGIT_SEGMENTS: [u8; 11] = [
+    0b00111111, // 0
+    0b00000110, // 1
+    0b01011011, // 2
+    0b01001111, // 3
+    0b01100110, // 4
+    0b01101101, // 5
+    0b01111101, // 6
+    0b00000111, // 7
+    0b01111111, // 8
+    0b01101111, // 9
+    0b01000000, // -
+];
+
+fn char_segments(c: char) -> u8 {
+    match c.to_ascii_uppercase() {
+        'A' => 0b01110111,
+        'B' => 0b01111100,
+        'C' => 0b00111001,
+        'D' => 0b01011110,
+        'E' => 0b01111001,
+        'F' => 0b01110001,
+        'G' => 0b00111101,
+        'H' => 0b01110110,
+        'I' => 0b00000110,
+        'J' => 0b00011110,
+        'K' => 0b01110110, // no true 7-seg K, closest to H
+        'L' => 0b00111000,
+        'M' => 0b00110111, // no true 7-seg M
+        'N' => 0b01010100,
+        'O' => 0b00111111,
+        'P' => 0b01110011,
+        'Q' => 0b01100111,
+        'R' => 0b01010000,
+        'S' => 0b01101101,
+        'T' => 0b01111000,
+        'U' => 0b00111110,
+        'V' => 0b00111110, // no true 7-seg V
+        'W' => 0b00101010, // no true 7-seg W
+        'X' => 0b01110110, // no true 7-seg X
+        'Y' => 0b01101110,
+        'Z' => 0b01011011,
+        '-' => DIGIT_SEGMENTS[10],
+        _ => 0b00000000, // space and anything unmapped -> blank
+    }
 }
 
 struct TM1637Display {
     clk: Output<'static>,
-    dio: Output<'static>,
+    dio: Flex<'static>,
 }
 
 impl TM1637Display {
-    fn new(clk: Output<'static>, dio: Output<'static>) -> Self {
+    fn new(clk: Output<'static>, mut dio: Flex<'static>) -> Self {
+        dio.set_high();
+        dio.set_as_output();
         Self { clk, dio }
     }
 
-    async fn show_number(&mut self, number: u32) {
-        // Simplified TM1637 implementation
-        info!("Display: {}", number);
-        
-        // In real implementation, would send actual TM1637 commands
-        // For now, just pulse the pins to show activity
+    async fn start(&mut self) {
+        self.dio.set_as_output();
+        self.dio.set_high();
+        self.clk.set_high();
+        Timer::after(Duration::from_micros(2)).await;
+        self.dio.set_low();
+    }
+
+    async fn stop(&mut self) {
+        self.dio.set_as_output();
+        self.clk.set_low();
+        Timer::after(Duration::from_micros(2)).await;
+        self.dio.set_low();
+        Timer::after(Duration::from_micros(2)).await;
+        self.clk.set_high();
+        Timer::after(Duration::from_micros(2)).await;
+        self.dio.set_high();
+    }
+
+    // Sends a byte LSB-first and returns the ACK bit sampled on the 9th clock.
+    async fn write_byte(&mut self, mut byte: u8) -> bool {
+        self.dio.set_as_output();
         for _ in 0..8 {
-            self.clk.set_high();
-            Timer::after(Duration::from_micros(1)).await;
             self.clk.set_low();
-            Timer::after(Duration::from_micros(1)).await;
+            if byte & 0x01 != 0 {
+                self.dio.set_high();
+            } else {
+                self.dio.set_low();
+            }
+            Timer::after(Duration::from_micros(2)).await;
+            self.clk.set_high();
+            Timer::after(Duration::from_micros(2)).await;
+            byte >>= 1;
         }
+
+        // 9th clock: release DIO and let the display pull it low to ACK.
+        self.clk.set_low();
+        self.dio.set_as_input();
+        Timer::after(Duration::from_micros(2)).await;
+        let ack = self.dio.is_low();
+        self.clk.set_high();
+        Timer::after(Duration::from_micros(2)).await;
+        self.clk.set_low();
+        self.dio.set_as_output();
+
+        ack
+    }
+
+    async fn write_command(&mut self, cmd: u8) {
+        self.start().await;
+        self.write_byte(cmd).await;
+        self.stop().await;
+    }
+
+    async fn write_digits(&mut self, segments: &[u8; 4]) {
+        self.start().await;
+        self.write_byte(CMD_ADDRESS_BASE).await;
+        for &seg in segments {
+            self.write_byte(seg).await;
+        }
+        self.stop().await;
+    }
+
+    async fn render(&mut self, segments: [u8; 4]) {
+        self.write_command(CMD_DATA_AUTO_INCREMENT).await;
+        self.write_digits(&segments).await;
+        self.write_command(CMD_DISPLAY_CONTROL | DEFAULT_BRIGHTNESS).await;
+    }
+
+    async fn show_number(&mut self, number: u32) {
+        info!("Display: {}", number);
+
+        let n = number.min(9999);
+        let segments = [
+            DIGIT_SEGMENTS[(n / 1000 % 10) as usize],
+            DIGIT_SEGMENTS[(n / 100 % 10) as usize],
+            DIGIT_SEGMENTS[(n / 10 % 10) as usize],
+            DIGIT_SEGMENTS[(n % 10) as usize],
+        ];
+
+        self.render(segments).await;
     }
 
     async fn show_text(&mut self, text: &str) {
         info!("Display: {}", text);
-        
-        // Pulse pins for activity indication
-        for _ in 0..16 {
-            self.dio.set_high();
-            Timer::after(Duration::from_micros(1)).await;
-            self.dio.set_low();
-            Timer::after(Duration::from_micros(1)).await;
+
+        let mut segments = [0u8; 4];
+        for (slot, c) in segments.iter_mut().zip(text.chars()) {
+            *slot = char_segments(c);
         }
+
+        self.render(segments).await;
     }
 }
 
-struct RgbLeds {
-    red_pin: Output<'static>,
-    green_pin: Output<'static>,
-    blue_pin: Output<'static>,
+// One PWM channel, tracking its own config so duty/top updates don't have to
+// rebuild it from scratch.
+struct PwmChannel {
+    pwm: Pwm<'static>,
+    config: PwmConfig,
 }
 
-impl RgbLeds {
-    fn new(red_pin: Output<'static>, green_pin: Output<'static>, blue_pin: Output<'static>) -> Self {
-        Self { red_pin, green_pin, blue_pin }
+impl PwmChannel {
+    fn new(pwm: Pwm<'static>, config: PwmConfig) -> Self {
+        Self { pwm, config }
     }
+}
 
-    async fn set_color(&mut self, rgb: [u8; 3]) {
-        info!("LEDs: RGB({}, {}, {})", rgb[0], rgb[1], rgb[2]);
-        
-        // Set RGB pins based on color values
-        // For simplicity, using digital on/off (not PWM)
-        if rgb[0] > 128 {
-            self.red_pin.set_high();
-        } else {
-            self.red_pin.set_low();
-        }
-        
-        if rgb[1] > 128 {
-            self.green_pin.set_high();
-        } else {
-            self.green_pin.set_low();
-        }
-        
-        if rgb[2] > 128 {
-            self.blue_pin.set_high();
-        } else {
-            self.blue_pin.set_low();
+// Number of addressable pixels in the ring.
+const NUM_PIXELS: usize = 8;
+
+struct LedRing<'d> {
+    ws2812: PioWs2812<'d, PIO0, 0, NUM_PIXELS>,
+    pixels: [RGB8; NUM_PIXELS],
+}
+
+impl<'d> LedRing<'d> {
+    fn new(ws2812: PioWs2812<'d, PIO0, 0, NUM_PIXELS>) -> Self {
+        Self { ws2812, pixels: [RGB8::default(); NUM_PIXELS] }
+    }
+
+    fn set_pixel(&mut self, index: usize, color: RGB8) {
+        if index < NUM_PIXELS {
+            self.pixels[index] = color;
         }
     }
 
+    async fn render(&mut self) {
+        self.ws2812.write(&self.pixels).await;
+    }
+
+    async fn fill(&mut self, color: RGB8) {
+        self.pixels = [color; NUM_PIXELS];
+        self.render().await;
+    }
+
     async fn clear(&mut self) {
-        self.set_color([0, 0, 0]).await;
+        self.fill(RGB8::default()).await;
+    }
+}
+
+// Classic NeoPixel color wheel: 0-255 position around the hue circle.
+fn wheel(pos: u8) -> RGB8 {
+    if pos < 85 {
+        RGB8::new(255 - pos * 3, pos * 3, 0)
+    } else if pos < 170 {
+        let pos = pos - 85;
+        RGB8::new(0, 255 - pos * 3, pos * 3)
+    } else {
+        let pos = pos - 170;
+        RGB8::new(pos * 3, 0, 255 - pos * 3)
+    }
+}
+
+// System clock frequency used to derive PWM divider/top for a target tone.
+const SYS_CLK_HZ: u32 = 125_000_000;
+
+// Melodic cues: low -> high, used for the Perfect arpeggio and individual tones.
+const TONE_TABLE: [u32; 4] = [196, 262, 330, 784];
+
+// Picks the smallest integer clock divider (1-255) for which `top` still fits
+// a u16, then returns (divider, top). Needed because with the default divider
+// of 1 the slice can't count slowly enough to reach audio-range frequencies -
+// `top` would overflow 16 bits for every entry in `TONE_TABLE`.
+fn pwm_divider_and_top(freq_hz: u32) -> (u16, u16) {
+    let mut divider: u32 = 1;
+    while divider < 255 && SYS_CLK_HZ / (divider * freq_hz) > u16::MAX as u32 + 1 {
+        divider += 1;
+    }
+    let top = (SYS_CLK_HZ / (divider * freq_hz))
+        .saturating_sub(1)
+        .min(u16::MAX as u32) as u16;
+    (divider as u16, top)
+}
+
+struct Buzzer {
+    channel: PwmChannel,
+}
+
+impl Buzzer {
+    fn new(channel: PwmChannel) -> Self {
+        Self { channel }
+    }
+
+    // Plays `freq_hz` at ~50% duty for `duration`, then silences the buzzer.
+    async fn play_tone(&mut self, freq_hz: u32, duration: Duration) {
+        if freq_hz == 0 {
+            Timer::after(duration).await;
+            return;
+        }
+
+        let (divider, top) = pwm_divider_and_top(freq_hz);
+        self.channel.config.divider = FixedU16::<U4>::from_num(divider);
+        self.channel.config.top = top;
+        self.channel.config.compare_a = top / 2;
+        self.channel.config.compare_b = top / 2;
+        self.channel.pwm.set_config(&self.channel.config);
+
+        Timer::after(duration).await;
+
+        self.channel.config.compare_a = 0;
+        self.channel.config.compare_b = 0;
+        self.channel.pwm.set_config(&self.channel.config);
     }
 }
\ No newline at end of file